@@ -1,7 +1,7 @@
 use anyhow::Result;
 use serde_json::Value;
-use turbo_tasks::{primitives::StringVc, TryJoinIterExt};
-use turbo_tasks_fs::FileSystemPathVc;
+use turbo_tasks::primitives::StringVc;
+use turbo_tasks_fs::{File, FileSystemPathVc};
 use turbopack::ecmascript::{
     chunk::{
         EcmascriptChunkItem, EcmascriptChunkItemContent, EcmascriptChunkItemContentVc,
@@ -11,13 +11,16 @@ use turbopack::ecmascript::{
     utils::stringify_js,
 };
 use turbopack_core::{
-    asset::{Asset, AssetContentVc, AssetVc},
+    asset::{Asset, AssetContent, AssetContentVc, AssetVc},
     chunk::{
         available_assets::AvailableAssetsVc, Chunk, ChunkGroupReferenceVc, ChunkGroupVc, ChunkItem,
         ChunkItemVc, ChunkVc, ChunkableAsset, ChunkableAssetVc, ChunkingContextVc,
     },
+    code_builder::{CodeBuilder, CodeVc},
     ident::AssetIdentVc,
     reference::AssetReferencesVc,
+    source_map::{GenerateSourceMap, OptionSourceMapVc},
+    virtual_asset::VirtualAssetVc,
 };
 
 #[turbo_tasks::function]
@@ -25,11 +28,198 @@ fn modifier() -> StringVc {
     StringVc::cell("chunks".to_string())
 }
 
+#[turbo_tasks::function]
+fn manifest_modifier() -> StringVc {
+    StringVc::cell("chunks manifest".to_string())
+}
+
+#[turbo_tasks::value(transparent)]
+struct ClientChunkPaths(Vec<String>);
+
+// This deliberately stays on `Vec<String>` rather than `turbo_tasks::RcStr`:
+// every path here is immediately re-stringified into a `serde_json::Value`
+// or a JS source literal, so an `RcStr` would only add an allocation (the
+// RcStr itself) on top of the `String`/`Value` conversion it's reconverted
+// into, not remove one. The actual per-build cost this was meant to address
+// — re-resolving every chunk path on each call — is what `client_chunk_paths`
+// being a cached `#[turbo_tasks::function]` (below) fixes instead.
+
+/// Builds the `moduleId`/`chunks`/`ssrChunks` JSON object emitted by
+/// `chunks_manifest`. Pulled out as a plain function (no `Vc`s) so the
+/// shape of the manifest can be unit-tested without a turbo_tasks runtime.
+fn build_manifest(
+    module_id: &str,
+    chunks: &[String],
+    ssr_chunks: Option<&[String]>,
+) -> Value {
+    let mut manifest = serde_json::Map::new();
+    manifest.insert(
+        "moduleId".to_string(),
+        Value::String(module_id.to_string()),
+    );
+    manifest.insert(
+        "chunks".to_string(),
+        Value::Array(chunks.iter().cloned().map(Value::String).collect()),
+    );
+    if let Some(ssr_chunks) = ssr_chunks {
+        manifest.insert(
+            "ssrChunks".to_string(),
+            Value::Array(ssr_chunks.iter().cloned().map(Value::String).collect()),
+        );
+    }
+    Value::Object(manifest)
+}
+
+/// Builds the `__turbopack_esm__({ ... })` wrapper source emitted by
+/// `WithChunksChunkItemVc::code`. `module_id` must already be a JS
+/// expression (e.g. via `stringify_js`), not a bare id. Pulled out as a
+/// plain function so the generated exports/body can be unit-tested
+/// without a turbo_tasks runtime.
+fn build_chunk_wrapper_source(
+    module_id: &str,
+    chunks: &[String],
+    ssr_chunks: Option<&[String]>,
+) -> String {
+    let mut exports = format!(
+        "  default: () => {},
+  chunks: () => chunks",
+        module_id
+    );
+    let mut body = format!(
+        "const chunks = {};\n",
+        Value::Array(chunks.iter().cloned().map(Value::String).collect())
+    );
+
+    if let Some(ssr_chunks) = ssr_chunks {
+        exports.push_str(
+            ",
+  ssrChunks: () => ssrChunks",
+        );
+        body.push_str(&format!(
+            "const ssrChunks = {};\n",
+            Value::Array(ssr_chunks.iter().cloned().map(Value::String).collect())
+        ));
+    }
+
+    format!(
+        "__turbopack_esm__({{
+{}
+}});
+{}",
+        exports, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_without_ssr_chunks_omits_the_key() {
+        let manifest = build_manifest("123", &["chunks/foo.js".to_string()], None);
+        assert_eq!(
+            manifest,
+            serde_json::json!({
+                "moduleId": "123",
+                "chunks": ["chunks/foo.js"],
+            })
+        );
+    }
+
+    #[test]
+    fn manifest_with_ssr_chunks_includes_both_lists() {
+        let manifest = build_manifest(
+            "123",
+            &["chunks/foo.js".to_string()],
+            Some(&["chunks/ssr/foo.js".to_string()]),
+        );
+        assert_eq!(
+            manifest,
+            serde_json::json!({
+                "moduleId": "123",
+                "chunks": ["chunks/foo.js"],
+                "ssrChunks": ["chunks/ssr/foo.js"],
+            })
+        );
+    }
+
+    #[test]
+    fn wrapper_without_ssr_chunks_only_exports_default_and_chunks() {
+        let source = build_chunk_wrapper_source("123", &["chunks/foo.js".to_string()], None);
+        assert!(source.contains("default: () => 123"));
+        assert!(source.contains("chunks: () => chunks"));
+        assert!(!source.contains("ssrChunks"));
+        assert!(source.contains(r#"const chunks = ["chunks/foo.js"];"#));
+    }
+
+    #[test]
+    fn wrapper_with_ssr_chunks_exports_ssr_chunks_too() {
+        let source = build_chunk_wrapper_source(
+            "123",
+            &["chunks/foo.js".to_string()],
+            Some(&["chunks/ssr/foo.js".to_string()]),
+        );
+        assert!(source.contains("ssrChunks: () => ssrChunks"));
+        assert!(source.contains(r#"const ssrChunks = ["chunks/ssr/foo.js"];"#));
+    }
+}
+
+/// Resolves `group`'s chunk paths relative to `server_root`, in the same
+/// shape the generated ES module and the JSON manifest both need.
+///
+/// `group` must already be constructed with the right availability root
+/// (`WithChunksAsset::availability_root`) so that chunks covered by an
+/// enclosing chunk group are excluded from `group.chunks()` itself —
+/// filtering emitted *output* chunks against the *source-module*
+/// availability set tracked by `AvailableAssetsVc` doesn't work, since
+/// that set never contains output chunk assets.
+///
+/// This is a `turbo_tasks::function` (rather than a plain `async fn`) so
+/// that repeated calls with the same `group`/`server_root` are served
+/// from the task cache instead of re-resolving every chunk path on each
+/// recomputation.
+#[turbo_tasks::function]
+async fn client_chunk_paths(
+    group: ChunkGroupVc,
+    server_root: FileSystemPathVc,
+) -> Result<ClientChunkPathsVc> {
+    let chunks = group.chunks().await?;
+    let server_root = server_root.await?;
+    let mut client_chunks = Vec::new();
+    for chunk in chunks.iter() {
+        if let Some(path) = server_root.get_path_to(&*chunk.path().await?) {
+            client_chunks.push(path.to_string());
+        }
+    }
+    Ok(ClientChunkPathsVc::cell(client_chunks))
+}
+
 #[turbo_tasks::value(shared)]
 pub struct WithChunksAsset {
     pub asset: EcmascriptChunkPlaceableVc,
     pub server_root: FileSystemPathVc,
     pub chunking_context: ChunkingContextVc,
+    /// An optional ident that, if set, is used to name the chunk group
+    /// instead of `asset`'s ident. This keeps loader/wrapper chunk churn
+    /// from renaming the emitted chunk paths of the wrapped module.
+    pub ident: Option<AssetIdentVc>,
+    /// An optional chunking context that, if set, is used to additionally
+    /// chunk `asset` for server-side rendering, emitting a separate
+    /// `ssrChunks` export alongside the client `chunks` export.
+    pub ssr_chunking_context: Option<ChunkingContextVc>,
+    /// The availability root inherited from an enclosing chunk group via
+    /// `as_chunk`'s `current_availability_root`. Passed as the
+    /// availability root to every `ChunkGroupVc::from_asset` call so
+    /// chunks the enclosing group already loads are excluded from the
+    /// emitted chunk lists, instead of re-fetched or re-evaluated. Falls
+    /// back to `asset` itself (a standalone chunk group) when `None`.
+    pub availability_root: Option<AssetVc>,
+}
+
+impl WithChunksAsset {
+    fn availability_root(&self) -> AssetVc {
+        self.availability_root.unwrap_or_else(|| self.asset.into())
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -46,34 +236,106 @@ impl Asset for WithChunksAsset {
 
     #[turbo_tasks::function]
     async fn references(&self) -> Result<AssetReferencesVc> {
-        Ok(AssetReferencesVc::cell(vec![ChunkGroupReferenceVc::new(
-            ChunkGroupVc::from_asset(
-                self.asset.into(),
-                self.chunking_context,
-                None,
-                Some(self.asset.into()),
-            ),
+        let mut references = vec![ChunkGroupReferenceVc::new(ChunkGroupVc::from_asset(
+            self.asset.into(),
+            self.chunking_context,
+            self.ident,
+            Some(self.availability_root()),
+        ))
+        .into()];
+        if let Some(ssr_chunking_context) = self.ssr_chunking_context {
+            references.push(
+                ChunkGroupReferenceVc::new(ChunkGroupVc::from_asset(
+                    self.asset.into(),
+                    ssr_chunking_context,
+                    self.ident,
+                    Some(self.availability_root()),
+                ))
+                .into(),
+            );
+        }
+        Ok(AssetReferencesVc::cell(references))
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl WithChunksAssetVc {
+    /// Returns a standalone JSON asset mapping this module's id to its
+    /// client (and, if configured, SSR) chunk paths, so a Node/server
+    /// runtime can discover them without executing the generated JS.
+    #[turbo_tasks::function]
+    pub async fn chunks_manifest(self) -> Result<AssetVc> {
+        let this = self.await?;
+        let group = ChunkGroupVc::from_asset(
+            this.asset.into(),
+            this.chunking_context,
+            this.ident,
+            Some(this.availability_root()),
+        );
+        let client_chunks = client_chunk_paths(group, this.server_root).await?;
+
+        let module_id = &*this
+            .asset
+            .as_chunk_item(this.chunking_context)
+            .id()
+            .await?;
+
+        let ssr_chunks = if let Some(ssr_chunking_context) = this.ssr_chunking_context {
+            let ssr_group = ChunkGroupVc::from_asset(
+                this.asset.into(),
+                ssr_chunking_context,
+                this.ident,
+                Some(this.availability_root()),
+            );
+            Some(client_chunk_paths(ssr_group, this.server_root).await?)
+        } else {
+            None
+        };
+
+        let manifest = build_manifest(
+            &module_id.to_string(),
+            client_chunks.as_slice(),
+            ssr_chunks.as_ref().map(|c| c.as_slice()),
+        );
+        let content = serde_json::to_string_pretty(&manifest)?;
+        let ident = self.ident().with_modifier(manifest_modifier());
+        Ok(VirtualAssetVc::new(
+            ident.path(),
+            AssetContent::File(File::from(content).into()).cell(),
         )
-        .into()]))
+        .into())
     }
 }
 
 #[turbo_tasks::value_impl]
 impl ChunkableAsset for WithChunksAsset {
     #[turbo_tasks::function]
-    fn as_chunk(
+    async fn as_chunk(
         self_vc: WithChunksAssetVc,
         context: ChunkingContextVc,
         available_assets: Option<AvailableAssetsVc>,
         current_availability_root: Option<AssetVc>,
-    ) -> ChunkVc {
-        EcmascriptChunkVc::new(
+    ) -> Result<ChunkVc> {
+        let this = self_vc.await?;
+        let self_vc = if this.availability_root != current_availability_root {
+            WithChunksAssetVc::cell(WithChunksAsset {
+                asset: this.asset,
+                server_root: this.server_root,
+                chunking_context: this.chunking_context,
+                ident: this.ident,
+                ssr_chunking_context: this.ssr_chunking_context,
+                availability_root: current_availability_root,
+            })
+        } else {
+            self_vc
+        };
+        Ok(EcmascriptChunkVc::new(
             context,
             self_vc.as_ecmascript_chunk_placeable(),
             available_assets,
             current_availability_root,
         )
-        .into()
+        .into())
     }
 }
 
@@ -106,29 +368,18 @@ struct WithChunksChunkItem {
 }
 
 #[turbo_tasks::value_impl]
-impl EcmascriptChunkItem for WithChunksChunkItem {
-    #[turbo_tasks::function]
-    fn chunking_context(&self) -> ChunkingContextVc {
-        self.context
-    }
-
+impl WithChunksChunkItemVc {
     #[turbo_tasks::function]
-    async fn content(&self) -> Result<EcmascriptChunkItemContentVc> {
-        let inner = self.inner.await?;
+    async fn code(self) -> Result<CodeVc> {
+        let this = self.await?;
+        let inner = this.inner.await?;
         let group = ChunkGroupVc::from_asset(
             inner.asset.into(),
             inner.chunking_context,
-            None,
-            Some(inner.asset.into()),
+            inner.ident,
+            Some(inner.availability_root()),
         );
-        let chunks = group.chunks().await?;
-        let server_root = inner.server_root.await?;
-        let mut client_chunks = Vec::new();
-        for chunk_path in chunks.iter().map(|c| c.path()).try_join().await? {
-            if let Some(path) = server_root.get_path_to(&chunk_path) {
-                client_chunks.push(Value::String(path.to_string()));
-            }
-        }
+        let client_chunks = client_chunk_paths(group, inner.server_root).await?;
         let module_id = stringify_js(
             &*inner
                 .asset
@@ -136,24 +387,61 @@ impl EcmascriptChunkItem for WithChunksChunkItem {
                 .id()
                 .await?,
         );
+
+        let ssr_chunks = if let Some(ssr_chunking_context) = inner.ssr_chunking_context {
+            let ssr_group = ChunkGroupVc::from_asset(
+                inner.asset.into(),
+                ssr_chunking_context,
+                inner.ident,
+                Some(inner.availability_root()),
+            );
+            Some(client_chunk_paths(ssr_group, inner.server_root).await?)
+        } else {
+            None
+        };
+
+        let source = build_chunk_wrapper_source(
+            &module_id,
+            client_chunks.as_slice(),
+            ssr_chunks.as_ref().map(|c| c.as_slice()),
+        );
+
+        let mut code = CodeBuilder::default();
+        code.push_source(&source.into(), Some(inner.asset.ident().path()));
+        Ok(code.build().cell())
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl EcmascriptChunkItem for WithChunksChunkItem {
+    #[turbo_tasks::function]
+    fn chunking_context(&self) -> ChunkingContextVc {
+        self.context
+    }
+
+    #[turbo_tasks::function]
+    async fn content(self_vc: WithChunksChunkItemVc) -> Result<EcmascriptChunkItemContentVc> {
+        let code = self_vc.code().await?;
+        // `EcmascriptChunkItemContent` has no `source_map` field to populate here —
+        // the chunk assembler reaches the map through the `GenerateSourceMap` impl
+        // below instead, so leaving the rest of this struct at its defaults doesn't
+        // drop it.
         Ok(EcmascriptChunkItemContent {
-            inner_code: format!(
-                "__turbopack_esm__({{
-  default: () => {},
-  chunks: () => chunks
-}});
-const chunks = {};
-",
-                module_id,
-                Value::Array(client_chunks)
-            )
-            .into(),
+            inner_code: code.source_code().clone(),
             ..Default::default()
         }
         .cell())
     }
 }
 
+#[turbo_tasks::value_impl]
+impl GenerateSourceMap for WithChunksChunkItem {
+    #[turbo_tasks::function]
+    fn generate_source_map(self_vc: WithChunksChunkItemVc) -> OptionSourceMapVc {
+        self_vc.code().generate_source_map()
+    }
+}
+
 #[turbo_tasks::value_impl]
 impl ChunkItem for WithChunksChunkItem {
     #[turbo_tasks::function]